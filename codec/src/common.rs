@@ -0,0 +1,20 @@
+/// The kind of media a stream/codec carries, shared between the encoder
+/// and decoder registries so a frontend can wire a demuxed `Stream`
+/// straight to a matching codec without hardcoding names.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum StreamType {
+    Video,
+    Audio,
+    Subtitles,
+    Data,
+}
+
+#[derive(Debug)]
+pub struct Descr {
+    pub codec: &'static str,
+    pub name: &'static str,
+    pub desc: &'static str,
+    pub mime: &'static str,
+    pub kind: StreamType,
+    // TODO more fields regarding capabilities
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use data::packet::Packet;
+use data::frame::Frame;
+use data::value::Value;
+
+use common::{Descr, StreamType};
+use error::*;
+
+pub trait Decoder {
+    fn set_extradata(&mut self, extra: &[u8]);
+    fn send_packet(&mut self, pkt: &Packet) -> Result<()>;
+    fn receive_frame(&mut self) -> Result<Frame>;
+
+    fn set_option<'a>(&mut self, key: &str, val: Value<'a>) -> Result<()>;
+}
+
+pub struct Context {
+    dec: Box<Decoder>,
+}
+
+impl Context {
+    // TODO: More constructors
+    pub fn by_name(codecs: &Codecs, name: &str) -> Option<Context> {
+        if let Some(builder) = codecs.by_name(name) {
+            let dec = builder.create();
+            Some(Context { dec: dec })
+        } else {
+            None
+        }
+    }
+
+    pub fn set_extradata(&mut self, extra: &[u8]) {
+        self.dec.set_extradata(extra)
+    }
+    pub fn set_option<'a, V>(&mut self, key: &str, val: V) -> Result<()>
+        where V: Into<Value<'a>>
+    {
+        self.dec.set_option(key, val.into())
+    }
+    pub fn send_packet(&mut self, pkt: &Packet) -> Result<()> {
+        self.dec.send_packet(pkt)
+    }
+    // TODO: Return an Event?
+    pub fn receive_frame(&mut self) -> Result<Frame> {
+        self.dec.receive_frame()
+    }
+}
+
+pub trait Descriptor {
+    fn create(&self) -> Box<Decoder>;
+    fn describe<'a>(&'a self) -> &'a Descr;
+}
+
+pub struct Codecs {
+    list: HashMap<&'static str, Vec<&'static Descriptor>>
+}
+
+impl Codecs {
+    pub fn new() -> Codecs {
+        Codecs { list: HashMap::new() }
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&'static Descriptor> {
+        if let Some(descs) = self.list.get(name) {
+            Some(descs[0])
+        } else {
+            None
+        }
+    }
+
+    /// Looks up a decoder by the fourcc/codec string carried in a
+    /// demuxed `Stream`.
+    pub fn by_codec(&self, codec: &str) -> Option<&'static Descriptor> {
+        self.by_name(codec)
+    }
+
+    /// Returns every decoder registered for the given media kind.
+    pub fn by_media_type(&self, kind: StreamType) -> Vec<&'static Descriptor> {
+        self.list
+            .values()
+            .flat_map(|descs| descs.iter())
+            .filter(|d| d.describe().kind == kind)
+            .cloned()
+            .collect()
+    }
+
+    pub fn append(&mut self, desc: &'static Descriptor) {
+        let codec_name = desc.describe().codec;
+
+        self.list.entry(codec_name).or_insert(Vec::new()).push(desc);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod dummy {
+        use super::super::*;
+        use data::packet::Packet;
+        use data::frame::Frame;
+
+        struct Dec {
+            state: usize,
+            extradata: Option<Vec<u8>>,
+        }
+
+        pub struct Des {
+            descr: Descr,
+        }
+
+        impl Descriptor for Des {
+            fn create(&self) -> Box<Decoder> {
+                box Dec { state: 0, extradata: None }
+            }
+            fn describe<'a>(&'a self) -> &'a Descr {
+                &self.descr
+            }
+        }
+
+        impl Decoder for Dec {
+            fn set_extradata(&mut self, extra: &[u8]) {
+                self.extradata = Some(extra.to_vec());
+            }
+            fn send_packet(&mut self, _pkt: &Packet) -> Result<()> {
+                self.state += 1;
+                Ok(())
+            }
+            fn receive_frame(&mut self) -> Result<Frame> {
+                unimplemented!()
+            }
+            fn set_option<'a>(&mut self, _key: &str, _val: Value<'a>) -> Result<()> {
+                unimplemented!()
+            }
+        }
+
+        pub const DUMMY_DESCR: &Des = &Des {
+            descr: Descr {
+                codec: "dummy",
+                name: "dummy",
+                desc: "Dummy decoder",
+                mime: "x-application/dummy",
+                kind: StreamType::Video,
+            }
+        };
+    }
+    use self::dummy::DUMMY_DESCR;
+
+    #[test]
+    fn lookup() {
+        let mut codecs = Codecs::new();
+
+        codecs.append(DUMMY_DESCR);
+
+        let _dec = codecs.by_name("dummy");
+    }
+
+    #[test]
+    fn lookup_by_media_type() {
+        let mut codecs = Codecs::new();
+
+        codecs.append(DUMMY_DESCR);
+
+        assert_eq!(codecs.by_media_type(StreamType::Video).len(), 1);
+        assert_eq!(codecs.by_media_type(StreamType::Audio).len(), 0);
+    }
+}
@@ -5,8 +5,33 @@ use data::packet::Packet;
 use data::frame::Frame;
 use data::value::Value;
 
+use common::{Descr, StreamType};
 use error::*;
 
+/// What values an `OptionDescriptor` accepts.
+#[derive(Debug)]
+pub enum OptionKind {
+    /// Valid values lie within an inclusive range. Only `Value::U64`
+    /// bounds are currently validated by `validate_option`; a `Range`
+    /// over any other `Value` variant rejects every value, so use
+    /// `Any` for non-`U64` options until that's extended.
+    Range { min: Value<'static>, max: Value<'static> },
+    /// Valid values are one of a fixed set.
+    Enum(&'static [Value<'static>]),
+    /// Any value is accepted; its `Value` variant is not checked.
+    Any,
+}
+
+/// Describes one option an `Encoder` accepts, so a UI can build a
+/// settings form without knowing the concrete encoder.
+#[derive(Debug)]
+pub struct OptionDescriptor {
+    pub name: &'static str,
+    pub kind: OptionKind,
+    pub default: Value<'static>,
+    pub required: bool,
+}
+
 pub trait Encoder {
     fn get_extradata(&self) -> Option<Vec<u8>>;
     fn send_frame(&mut self, pkt: &Frame) -> Result<()>;
@@ -14,7 +39,8 @@ pub trait Encoder {
 
     fn validate(&mut self) -> Result<()>;
     fn set_option<'a>(&mut self, key: &str, val: Value<'a>) -> Result<()>;
-    // fn get_option(&mut self, key: &str) -> Option<Value>;
+    fn get_option(&self, key: &str) -> Option<Value>;
+    fn list_options(&self) -> &[OptionDescriptor];
 }
 
 pub struct Context {
@@ -37,8 +63,41 @@ impl Context {
     pub fn set_option<'a, V>(&mut self, key: &str, val: V) -> Result<()>
         where V: Into<Value<'a>>
     {
-        // TODO: support more options
-        self.enc.set_option(key, val.into())
+        let val = val.into();
+
+        let opt = match self.enc.list_options().iter().find(|opt| opt.name == key) {
+            Some(opt) => opt,
+            None => return Err(Error::InvalidData),
+        };
+
+        match validate_option(opt, &val) {
+            Ok(()) => self.enc.set_option(key, val),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_option(&self, key: &str) -> Option<Value> {
+        self.enc.get_option(key)
+    }
+
+    pub fn list_options(&self) -> &[OptionDescriptor] {
+        self.enc.list_options()
+    }
+
+    /// Checks that every option the encoder marked `required` has been
+    /// set, then runs the encoder's own `validate`. Fails with
+    /// `Error::InvalidData` on the first missing required option; that
+    /// error carries no option name yet (same as `set_option`'s),
+    /// so callers can't currently tell which one is missing from the
+    /// error alone.
+    pub fn validate(&mut self) -> Result<()> {
+        for opt in self.enc.list_options() {
+            if opt.required && self.enc.get_option(opt.name).is_none() {
+                return Err(Error::InvalidData);
+            }
+        }
+
+        self.enc.validate()
     }
 
     pub fn get_extradata(&mut self) -> Option<Vec<u8>> {
@@ -53,13 +112,25 @@ impl Context {
     }
 }
 
-#[derive(Debug)]
-pub struct Descr {
-    pub codec: &'static str,
-    pub name: &'static str,
-    pub desc: &'static str,
-    pub mime: &'static str,
-    // TODO more fields regarding capabilities
+/// Checks `val` against what an `OptionDescriptor` allows.
+fn validate_option(opt: &OptionDescriptor, val: &Value) -> Result<()> {
+    match opt.kind {
+        OptionKind::Any => Ok(()),
+        OptionKind::Enum(values) => {
+            if values.iter().any(|v| v == val) {
+                Ok(())
+            } else {
+                Err(Error::InvalidData)
+            }
+        }
+        // Only Value::U64 bounds are validated for now (see OptionKind::Range).
+        OptionKind::Range { ref min, ref max } => {
+            match (min, max, val) {
+                (&Value::U64(lo), &Value::U64(hi), &Value::U64(v)) if v >= lo && v <= hi => Ok(()),
+                _ => Err(Error::InvalidData),
+            }
+        }
+    }
 }
 
 pub trait Descriptor {
@@ -75,7 +146,6 @@ impl Codecs {
     pub fn new() -> Codecs {
         Codecs { list: HashMap::new() }
     }
-    // TODO more lookup functions
     pub fn by_name(&self, name: &str) -> Option<&'static Descriptor> {
         if let Some(descs) = self.list.get(name) {
             Some(descs[0])
@@ -84,6 +154,24 @@ impl Codecs {
         }
     }
 
+    /// Looks up an encoder by the fourcc/codec string carried in a
+    /// demuxed `Stream`. Equivalent to `by_name`, kept as a distinct
+    /// name so call sites read as "I have a codec id from a Stream"
+    /// rather than "I know the implementation's name".
+    pub fn by_codec(&self, codec: &str) -> Option<&'static Descriptor> {
+        self.by_name(codec)
+    }
+
+    /// Returns every encoder registered for the given media kind.
+    pub fn by_media_type(&self, kind: StreamType) -> Vec<&'static Descriptor> {
+        self.list
+            .values()
+            .flat_map(|descs| descs.iter())
+            .filter(|d| d.describe().kind == kind)
+            .cloned()
+            .collect()
+    }
+
     pub fn append(&mut self, desc: &'static Descriptor) {
         let codec_name = desc.describe().codec;
 
@@ -153,14 +241,48 @@ mod test {
                 Ok(())
             }
 
+            fn get_option(&self, key: &str) -> Option<Value> {
+                match key {
+                    "w" => self.w.map(|v| Value::U64(v as u64)),
+                    "h" => self.h.map(|v| Value::U64(v as u64)),
+                    "format" => self.format.clone().map(Value::Formaton),
+                    _ => None,
+                }
+            }
+
+            fn list_options(&self) -> &[OptionDescriptor] {
+                OPTIONS
+            }
         }
 
+        const OPTIONS: &'static [OptionDescriptor] = &[
+            OptionDescriptor {
+                name: "w",
+                kind: OptionKind::Range { min: Value::U64(1), max: Value::U64(7680) },
+                default: Value::U64(1920),
+                required: true,
+            },
+            OptionDescriptor {
+                name: "h",
+                kind: OptionKind::Range { min: Value::U64(1), max: Value::U64(4320) },
+                default: Value::U64(1080),
+                required: true,
+            },
+            OptionDescriptor {
+                name: "format",
+                kind: OptionKind::Any,
+                default: Value::U64(0),
+                required: true,
+            },
+        ];
+
         pub const DUMMY_DESCR: &Des = &Des {
             descr: Descr {
                 codec: "dummy",
                 name: "dummy",
                 desc: "Dummy encoder",
                 mime: "x-application/dummy",
+                kind: StreamType::Video,
             }
         };
     }
@@ -174,4 +296,48 @@ mod test {
 
         let _enc = codecs.by_name("dummy");
     }
+
+    #[test]
+    fn lookup_by_media_type() {
+        let mut codecs = Codecs::new();
+
+        codecs.append(DUMMY_DESCR);
+
+        assert_eq!(codecs.by_media_type(StreamType::Video).len(), 1);
+        assert_eq!(codecs.by_media_type(StreamType::Audio).len(), 0);
+    }
+
+    #[test]
+    fn set_option_rejects_unknown_key() {
+        let mut codecs = Codecs::new();
+        codecs.append(DUMMY_DESCR);
+        let mut ctx = Context::by_name(&codecs, "dummy").unwrap();
+
+        assert!(ctx.set_option("bogus", Value::U64(1)).is_err());
+    }
+
+    #[test]
+    fn set_option_rejects_out_of_range() {
+        let mut codecs = Codecs::new();
+        codecs.append(DUMMY_DESCR);
+        let mut ctx = Context::by_name(&codecs, "dummy").unwrap();
+
+        assert!(ctx.set_option("w", Value::U64(0)).is_err());
+        assert!(ctx.set_option("w", Value::U64(1920)).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_missing_required_option() {
+        let mut codecs = Codecs::new();
+        codecs.append(DUMMY_DESCR);
+        let mut ctx = Context::by_name(&codecs, "dummy").unwrap();
+
+        assert!(ctx.validate().is_err());
+
+        ctx.set_option("w", Value::U64(1920)).unwrap();
+        ctx.set_option("h", Value::U64(1080)).unwrap();
+        ctx.set_option("format", Value::Formaton(::std::rc::Rc::new(*::data::pixel::formats::YUV420))).unwrap();
+
+        assert!(ctx.validate().is_ok());
+    }
 }
@@ -15,6 +15,15 @@ pub enum Event {
     MoreDataNeeded
 }
 
+/// Timing note: `Stream`/`Packet` are expected to eventually grow a
+/// `time_base: timebase::TimeBase` and `pts`/`dts`/`duration` fields, so
+/// a demuxed packet carries its own clock rather than leaving A/V sync
+/// to guess. That part of the contract isn't implemented yet here
+/// (`Stream` and `Packet` live in the `stream`/`data` crates, outside
+/// this tree). `timebase::TimeBase` is already wired into the types this
+/// crate does own: `Packetiser::time_base` and `RawDemuxer::time_base`
+/// expose an elementary stream's clock the same way a `Stream::time_base`
+/// eventually will.
 pub trait Demuxer {
     fn open(&mut self);
     fn read_headers(&mut self, buf: &Box<Buffered>, info: &mut GlobalInfo) -> Result<SeekFrom>;
@@ -70,6 +79,77 @@ pub fn probe<'a>(demuxers: &[&'static DemuxerBuilder],
     }
 }
 
+/// Which signal decided the match returned by `detect`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum MatchSource {
+    /// Only the filename extension matched.
+    Extension,
+    /// The caller-supplied MIME type matched.
+    Mime,
+    /// The content probe matched the format structure outright.
+    Content,
+}
+
+/// Fuses filename extension, caller-supplied MIME type and content
+/// probing into a single best-guess format match.
+///
+/// Starts from `Score::EXTENSION` when `filename`'s suffix is listed in a
+/// builder's `DemuxerDescription::extensions`, raises to `Score::MIME`
+/// when `mime` matches `DemuxerDescription::mime`, and lets a strong
+/// content match (`Score::MAX`) override both. Returns the winning
+/// builder together with its score and which signal decided it, so a
+/// frontend can warn on a mismatch (e.g. a `.avi` extension whose
+/// content actually probes as Matroska).
+pub fn detect<'a>(demuxers: &[&'a DemuxerBuilder],
+                   filename: Option<&str>,
+                   mime: Option<&str>,
+                   data: &[u8])
+                   -> Option<(&'a DemuxerBuilder, u8, MatchSource)> {
+    // `rsplit` yields the whole string when there's no separator, so an
+    // extensionless filename must not be treated as its own extension.
+    let ext = filename.and_then(|f| {
+        if f.contains('.') {
+            f.rsplit('.').next()
+        } else {
+            None
+        }
+    });
+
+    let mut best: Option<(&'a DemuxerBuilder, u8, MatchSource)> = None;
+
+    for builder in demuxers {
+        let desc = builder.describe();
+        let mut score = 0u8;
+        let mut source = MatchSource::Extension;
+
+        if let Some(ext) = ext {
+            if desc.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+                score = Score::EXTENSION as u8;
+                source = MatchSource::Extension;
+            }
+        }
+
+        if let Some(mime) = mime {
+            if desc.mime.iter().any(|m| *m == mime) && Score::MIME as u8 > score {
+                score = Score::MIME as u8;
+                source = MatchSource::Mime;
+            }
+        }
+
+        let content_score = builder.probe(data);
+        if content_score >= Score::MAX as u8 && content_score > score {
+            score = content_score;
+            source = MatchSource::Content;
+        }
+
+        if score > 0 && best.map_or(true, |(_, best_score, _)| score > best_score) {
+            best = Some((*builder, score, source));
+        }
+    }
+
+    best
+}
+
 #[macro_export]
 macro_rules! module {
     {
@@ -161,4 +241,47 @@ mod test {
             None => panic!(),
         };
     }
+
+    #[test]
+    fn detect_by_extension_only() {
+        let buf = [1; PROBE_DATA];
+
+        match detect(&DEMUXER_BUILDERS, Some("movie.test"), None, &buf) {
+            Some((_, score, MatchSource::Extension)) => assert_eq!(score, Score::EXTENSION as u8),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn detect_content_overrides_extension() {
+        let mut buf = [1; PROBE_DATA];
+        buf[0] = 0;
+
+        match detect(&DEMUXER_BUILDERS, Some("movie.avi"), None, &buf) {
+            Some((_, score, MatchSource::Content)) => assert_eq!(score, Score::MAX as u8),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn detect_extensionless_filename_is_not_an_extension_match() {
+        let buf = [1; PROBE_DATA];
+
+        // "test" has no '.', so it must not be read as the "test" extension
+        // even though that happens to be one of the Test builder's.
+        match detect(&DEMUXER_BUILDERS, Some("test"), None, &buf) {
+            Some(_) => panic!(),
+            None => (),
+        };
+    }
+
+    #[test]
+    fn detect_no_match() {
+        let buf = [1; PROBE_DATA];
+
+        match detect(&DEMUXER_BUILDERS, None, None, &buf) {
+            Some(_) => panic!(),
+            None => (),
+        };
+    }
 }
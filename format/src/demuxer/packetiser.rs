@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+
+use data::packet::Packet;
+use stream::Stream;
+use demuxer::demux::Score;
+use demuxer::timebase::TimeBase;
+use error::*;
+
+/// Splits a codec-aware elementary bytestream (Annex-B H.264, ADTS AAC,
+/// raw MP3, ...) into `Packet`s. Unlike a `Demuxer`, a `Packetiser` has no
+/// notion of seeking or multiple streams: it is fed bytes in order and
+/// hands back whatever complete packets it can carve out of them.
+pub trait Packetiser {
+    fn push_data(&mut self, data: &[u8]);
+    fn get_packet(&mut self) -> Result<Option<Packet>>;
+    fn reset(&mut self);
+
+    /// Returns the stream's parameters (codec, channels/dimensions, ...)
+    /// once enough data has gone through `push_data` to infer them, and
+    /// `None` every other time, mirroring how `get_packet` only yields a
+    /// packet when one is actually complete. `RawDemuxer` surfaces this
+    /// as the raw-stream equivalent of a container's `Event::NewStream`.
+    fn stream_info(&mut self) -> Option<Stream>;
+
+    /// The packetiser's native clock (e.g. a codec's sample rate, or
+    /// `1/90000` for MPEG-style systems streams). `RawDemuxer` exposes
+    /// this through `RawDemuxer::time_base` so a frontend can interpret
+    /// the packets it gets out of a raw stream the same way it would a
+    /// container's.
+    fn time_base(&self) -> TimeBase;
+}
+
+pub struct PacketiserDescription {
+    pub name:       &'static str,
+    pub extensions: &'static [&'static str],
+}
+
+pub trait PacketiserBuilder {
+    fn describe(&self) -> &'static PacketiserDescription;
+    fn probe(&self, data: &[u8]) -> u8;
+    // cannot use impl Packetiser as return type of a trait method yet
+    fn alloc(&self) -> Box<Packetiser>;
+}
+
+/// Picks the packetiser whose probe scores highest against the head
+/// bytes of a raw stream, reusing the same scoring convention as the
+/// container `probe` in `demux`.
+pub fn probe<'a>(packetisers: &[&'a PacketiserBuilder],
+                  data: &[u8])
+                  -> Option<&'a PacketiserBuilder> {
+    let mut max = u8::min_value();
+    let mut candidate: Option<&PacketiserBuilder> = None;
+    for builder in packetisers {
+        let score = builder.probe(data);
+
+        if score > max {
+            max = score;
+            candidate = Some(*builder);
+        }
+    }
+
+    if max > Score::EXTENSION as u8 {
+        candidate
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(dead_code)]
+    #![allow(unused_variables)]
+    use super::*;
+
+    struct TestPacketiser {
+        pushed: usize,
+    }
+
+    impl Packetiser for TestPacketiser {
+        fn push_data(&mut self, data: &[u8]) {
+            self.pushed += data.len();
+        }
+        fn get_packet(&mut self) -> Result<Option<Packet>> {
+            if self.pushed > 0 {
+                self.pushed = 0;
+                Ok(Some(Packet::with_capacity(1)))
+            } else {
+                Ok(None)
+            }
+        }
+        fn reset(&mut self) {
+            self.pushed = 0;
+        }
+        fn stream_info(&mut self) -> Option<Stream> {
+            None
+        }
+        fn time_base(&self) -> TimeBase {
+            TimeBase::new(1, 1000)
+        }
+    }
+
+    struct TestPacketiserBuilder {}
+
+    impl PacketiserBuilder for TestPacketiserBuilder {
+        fn describe(&self) -> &'static PacketiserDescription {
+            const D: &'static PacketiserDescription = &PacketiserDescription {
+                name: "Test",
+                extensions: &["test", "t"],
+            };
+
+            D
+        }
+        fn probe(&self, data: &[u8]) -> u8 {
+            if data[0] == 0 {
+                Score::MAX as u8
+            } else {
+                0
+            }
+        }
+        fn alloc(&self) -> Box<Packetiser> {
+            box TestPacketiser { pushed: 0 }
+        }
+    }
+
+    const PACKETISER_BUILDERS: [&'static PacketiserBuilder; 1] = [&TestPacketiserBuilder {}];
+
+    #[test]
+    fn probe_packetiser() {
+        let mut buf = [1; 4];
+
+        match probe(&PACKETISER_BUILDERS, &buf) {
+            Some(_) => panic!(),
+            None => (),
+        };
+
+        buf[0] = 0;
+
+        match probe(&PACKETISER_BUILDERS, &buf) {
+            Some(_) => (),
+            None => panic!(),
+        };
+    }
+}
@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use std::io::SeekFrom;
+
+use buffer::Buffered;
+use demuxer::context::GlobalInfo;
+use demuxer::demux::{Demuxer, Event};
+use demuxer::packetiser::Packetiser;
+use demuxer::timebase::TimeBase;
+use error::*;
+
+/// A demuxer for elementary/raw bytestreams (bare `.h264`, `.aac`, `.mp3`,
+/// ...) that carry no container framing of their own: packets are carved
+/// out by a codec-aware `Packetiser` fed straight from the input buffer,
+/// rather than parsed from box/chunk structure.
+pub struct RawDemuxer {
+    packetiser: Box<Packetiser>,
+    stream_sent: bool,
+}
+
+impl RawDemuxer {
+    pub fn new(packetiser: Box<Packetiser>) -> RawDemuxer {
+        RawDemuxer { packetiser: packetiser, stream_sent: false }
+    }
+
+    /// The announced stream's native clock, taken straight from the
+    /// underlying `Packetiser`.
+    pub fn time_base(&self) -> TimeBase {
+        self.packetiser.time_base()
+    }
+}
+
+impl Demuxer for RawDemuxer {
+    fn open(&mut self) {}
+
+    fn read_headers(&mut self, _buf: &Box<Buffered>, _info: &mut GlobalInfo) -> Result<SeekFrom> {
+        // Raw streams carry no container-level header; the packetiser
+        // itself infers stream parameters as packets go by.
+        Ok(SeekFrom::Current(0))
+    }
+
+    fn read_packet(&mut self, buf: &Box<Buffered>) -> Result<(SeekFrom, Event)> {
+        let data = buf.data();
+
+        if !data.is_empty() {
+            self.packetiser.push_data(data);
+        }
+
+        let consumed = SeekFrom::Current(data.len() as i64);
+
+        // Mirror the container Demuxer contract: announce the stream
+        // once the packetiser can describe it, before handing out
+        // packets for it. The announcement is returned on its own: a
+        // packet completed by the same push stays in the packetiser and
+        // comes out of get_packet() on the next call, since stream_sent
+        // is now true and every later call goes straight to it.
+        let new_stream = if !self.stream_sent {
+            self.packetiser.stream_info().map(|stream| {
+                self.stream_sent = true;
+                stream
+            })
+        } else {
+            None
+        };
+
+        if let Some(stream) = new_stream {
+            return Ok((consumed, Event::NewStream(stream)));
+        }
+
+        match try!(self.packetiser.get_packet()) {
+            Some(pkt) => Ok((consumed, Event::NewPacket(pkt))),
+            None => Ok((consumed, Event::MoreDataNeeded)),
+        }
+    }
+}
+
+/// Unifies a container `Demuxer` and a raw `Demuxer` + `Packetiser` pair
+/// behind one type, so a frontend can run the same read loop regardless
+/// of whether the input turned out to be a container or a bare
+/// elementary stream.
+pub enum DemuxerObject {
+    Demuxer(Box<Demuxer>),
+    Raw(RawDemuxer),
+}
+
+impl DemuxerObject {
+    pub fn open(&mut self) {
+        match *self {
+            DemuxerObject::Demuxer(ref mut d) => d.open(),
+            DemuxerObject::Raw(ref mut d) => d.open(),
+        }
+    }
+
+    pub fn read_headers(&mut self, buf: &Box<Buffered>, info: &mut GlobalInfo) -> Result<SeekFrom> {
+        match *self {
+            DemuxerObject::Demuxer(ref mut d) => d.read_headers(buf, info),
+            DemuxerObject::Raw(ref mut d) => d.read_headers(buf, info),
+        }
+    }
+
+    pub fn read_packet(&mut self, buf: &Box<Buffered>) -> Result<(SeekFrom, Event)> {
+        match *self {
+            DemuxerObject::Demuxer(ref mut d) => d.read_packet(buf),
+            DemuxerObject::Raw(ref mut d) => d.read_packet(buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(dead_code)]
+    #![allow(unused_variables)]
+    use super::*;
+    use data::packet::Packet;
+    use stream::Stream;
+
+    /// A `Buffered` that only ever hands out the data it was constructed
+    /// with; `RawDemuxer` never writes through it.
+    struct MockBuf {
+        data: Vec<u8>,
+    }
+
+    impl Buffered for MockBuf {
+        fn data(&self) -> &[u8] {
+            &self.data
+        }
+        fn write(&mut self, _data: &[u8]) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    /// Reports stream info and a packet simultaneously on the first
+    /// non-empty push, mirroring a packetiser that can infer the
+    /// stream's parameters from the very data that also completes its
+    /// first packet.
+    struct MockPacketiser {
+        pushed: bool,
+        stream_info_taken: bool,
+        packet_taken: bool,
+    }
+
+    impl Packetiser for MockPacketiser {
+        fn push_data(&mut self, _data: &[u8]) {
+            self.pushed = true;
+        }
+        fn get_packet(&mut self) -> Result<Option<Packet>> {
+            if self.pushed && !self.packet_taken {
+                self.packet_taken = true;
+                Ok(Some(Packet::with_capacity(1)))
+            } else {
+                Ok(None)
+            }
+        }
+        fn reset(&mut self) {
+            self.pushed = false;
+            self.packet_taken = false;
+        }
+        fn stream_info(&mut self) -> Option<Stream> {
+            if self.pushed && !self.stream_info_taken {
+                self.stream_info_taken = true;
+                Some(Stream::new(0))
+            } else {
+                None
+            }
+        }
+        fn time_base(&self) -> TimeBase {
+            TimeBase::new(1, 1000)
+        }
+    }
+
+    #[test]
+    fn stream_and_trailing_packet_both_survive_the_same_push() {
+        let packetiser = MockPacketiser { pushed: false, stream_info_taken: false, packet_taken: false };
+        let mut demuxer = RawDemuxer::new(box packetiser);
+
+        let full: Box<Buffered> = box MockBuf { data: vec![0; 4] };
+        let (_, ev) = demuxer.read_packet(&full).unwrap();
+        match ev {
+            Event::NewStream(_) => (),
+            _ => panic!("expected NewStream, got {:?}", ev),
+        }
+
+        // EOF: no more data, but the packet that completed alongside the
+        // stream info above must still come out instead of being lost.
+        let empty: Box<Buffered> = box MockBuf { data: Vec::new() };
+        let (_, ev) = demuxer.read_packet(&empty).unwrap();
+        match ev {
+            Event::NewPacket(_) => (),
+            _ => panic!("expected NewPacket, got {:?}", ev),
+        }
+    }
+}
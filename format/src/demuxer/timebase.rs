@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+/// A rational time base, e.g. `1/1000` for millisecond-resolution
+/// timestamps: a timestamp of `n` in this base means `n * num / den`
+/// seconds.
+///
+/// Intended to back a `time_base` field on `Stream` and `pts`/`dts`/
+/// `duration` fields on `Packet` (both defined in the `stream`/`data`
+/// crates, outside this tree) so demuxed packets carry a clock. A
+/// `TimeBase` of `0/0` means "no usable clock" and the conversions below
+/// treat it as such rather than dividing by zero.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct TimeBase {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl TimeBase {
+    pub fn new(num: u32, den: u32) -> TimeBase {
+        TimeBase { num: num, den: den }
+    }
+
+    /// Converts a timestamp expressed in this time base to seconds.
+    /// Returns `0.0` for a `0/0` (no usable clock) time base.
+    pub fn to_seconds(&self, ts: i64) -> f64 {
+        if self.den == 0 {
+            return 0.0;
+        }
+
+        (ts as f64) * (self.num as f64) / (self.den as f64)
+    }
+
+    /// Rescales a timestamp from this time base into `other`. Returns
+    /// `0` if either time base is `0/0` (no usable clock) rather than
+    /// dividing by zero.
+    pub fn rescale(&self, ts: i64, other: &TimeBase) -> i64 {
+        if self.den == 0 || other.num == 0 {
+            return 0;
+        }
+
+        (ts * self.num as i64 * other.den as i64) / (self.den as i64 * other.num as i64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_seconds() {
+        let tb = TimeBase::new(1, 1000);
+
+        assert_eq!(tb.to_seconds(1500), 1.5);
+    }
+
+    #[test]
+    fn rescale() {
+        let ms = TimeBase::new(1, 1000);
+        let us = TimeBase::new(1, 1_000_000);
+
+        assert_eq!(ms.rescale(10, &us), 10_000);
+        assert_eq!(us.rescale(10_000, &ms), 10);
+    }
+
+    #[test]
+    fn no_usable_clock_does_not_panic() {
+        let none = TimeBase::new(0, 0);
+        let ms = TimeBase::new(1, 1000);
+
+        assert_eq!(none.to_seconds(42), 0.0);
+        assert_eq!(none.rescale(42, &ms), 0);
+        assert_eq!(ms.rescale(42, &none), 0);
+    }
+}
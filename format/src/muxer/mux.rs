@@ -0,0 +1,176 @@
+#![allow(dead_code)]
+
+use std::io::SeekFrom;
+
+use buffer::Buffered;
+use data::packet::Packet;
+use demuxer::context::GlobalInfo;
+use error::*;
+
+/// The writing-side counterpart of `Demuxer`: takes `Packet`s for one or
+/// more streams and serialises them into a container, writing through a
+/// seekable `Buffered` sink so formats that need to patch up a header
+/// (size fields, seek tables, ...) can do so in `write_trailer`.
+pub trait Muxer {
+    fn write_header(&mut self, buf: &mut Box<Buffered>, info: &GlobalInfo) -> Result<SeekFrom>;
+    fn write_packet(&mut self, buf: &mut Box<Buffered>, pkt: &Packet) -> Result<SeekFrom>;
+    fn write_trailer(&mut self, buf: &mut Box<Buffered>) -> Result<SeekFrom>;
+}
+
+pub struct MuxerDescription {
+    pub name:       &'static str,
+    pub extensions: &'static [&'static str],
+    pub mime:       &'static [&'static str],
+}
+
+pub trait MuxerBuilder {
+    fn describe(&self) -> &'static MuxerDescription;
+    // cannot use impl Muxer as return type of a trait method yet
+    fn alloc(&self) -> Box<Muxer>;
+}
+
+/// Looks up the muxer builder registered under the given format name.
+pub fn by_name<'a>(muxers: &[&'a MuxerBuilder], name: &str) -> Option<&'a MuxerBuilder> {
+    muxers.iter().find(|b| b.describe().name == name).map(|b| *b)
+}
+
+/// Looks up a muxer builder able to produce a container matching the
+/// given filename extension.
+pub fn by_extension<'a>(muxers: &[&'a MuxerBuilder], ext: &str) -> Option<&'a MuxerBuilder> {
+    muxers.iter()
+        .find(|b| b.describe().extensions.iter().any(|e| *e == ext))
+        .map(|b| *b)
+}
+
+#[macro_export]
+macro_rules! muxer {
+    {
+        ($name:ident) {
+            write_header($whs:ident, $whctx:ident, $whi:ident) => $whb:block
+            write_packet($wps:ident, $wpctx:ident, $wpp:ident) => $wpb:block
+            write_trailer($wts:ident, $wtctx:ident) => $wtb:block
+
+            describe($ds:ident) => $db:block
+            alloc($asel:ident) => $ab:block
+        }
+    } => {
+        interpolate_idents! {
+            struct [$name Muxer];
+            struct [$name MuxerBuilder];
+
+            impl Muxer for [$name Muxer] {
+                fn write_header(&mut $whs, $whctx: &mut Box<Buffered>, $whi: &GlobalInfo) -> Result<SeekFrom> $whb
+                fn write_packet(&mut $wps, $wpctx: &mut Box<Buffered>, $wpp: &Packet) -> Result<SeekFrom> $wpb
+                fn write_trailer(&mut $wts, $wtctx: &mut Box<Buffered>) -> Result<SeekFrom> $wtb
+            }
+
+            impl MuxerBuilder for [$name MuxerBuilder] {
+                fn describe(&$ds) -> &'static MuxerDescription $db
+                fn alloc(&$asel) -> Box<Muxer> $ab
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(dead_code)]
+    #![allow(unused_variables)]
+    use super::*;
+
+    /// A `Buffered` stand-in that just appends to a `Vec<u8>`, enough to
+    /// exercise a `Muxer` writing into a sink.
+    struct TestBuf {
+        data: Vec<u8>,
+    }
+
+    impl Buffered for TestBuf {
+        fn data(&self) -> &[u8] {
+            &self.data
+        }
+        fn write(&mut self, data: &[u8]) -> Result<()> {
+            self.data.extend_from_slice(data);
+            Ok(())
+        }
+    }
+
+    muxer! {
+        (Test) {
+            write_header(self, buf, info) => {
+                try!(buf.write(b"HEAD"));
+                Ok(SeekFrom::Current(4))
+            }
+            write_packet(self, buf, pkt) => {
+                try!(buf.write(&pkt.data));
+                Ok(SeekFrom::Current(pkt.data.len() as i64))
+            }
+            write_trailer(self, buf) => {
+                try!(buf.write(b"TAIL"));
+                Ok(SeekFrom::Current(4))
+            }
+
+            describe(self) => {
+                const D: &'static MuxerDescription = &MuxerDescription {
+                    name: "Test",
+                    extensions: &["test", "t"],
+                    mime: &["x-application/test"],
+                };
+
+                D
+            }
+
+            alloc(self) => {
+                let mux = TestMuxer {};
+
+                box mux
+            }
+        }
+    }
+
+    const MUXER_BUILDERS: [&'static MuxerBuilder; 1] = [&TestMuxerBuilder {}];
+
+    #[test]
+    fn lookup_by_name() {
+        match by_name(&MUXER_BUILDERS, "Test") {
+            Some(_) => (),
+            None => panic!(),
+        };
+
+        match by_name(&MUXER_BUILDERS, "Nope") {
+            Some(_) => panic!(),
+            None => (),
+        };
+    }
+
+    #[test]
+    fn lookup_by_extension() {
+        match by_extension(&MUXER_BUILDERS, "t") {
+            Some(_) => (),
+            None => panic!(),
+        };
+
+        match by_extension(&MUXER_BUILDERS, "xyz") {
+            Some(_) => panic!(),
+            None => (),
+        };
+    }
+
+    #[test]
+    fn write_header_and_packet_hit_the_sink() {
+        let builder = by_name(&MUXER_BUILDERS, "Test").unwrap();
+        let mut mux = builder.alloc();
+        let mut buf: Box<Buffered> = box TestBuf { data: Vec::new() };
+        let info = GlobalInfo::default();
+
+        mux.write_header(&mut buf, &info).unwrap();
+        assert_eq!(buf.data(), b"HEAD");
+
+        let mut pkt = Packet::with_capacity(3);
+        pkt.data.extend_from_slice(b"PKT");
+        mux.write_packet(&mut buf, &pkt).unwrap();
+        assert_eq!(buf.data(), b"HEADPKT");
+
+        mux.write_trailer(&mut buf).unwrap();
+        assert_eq!(buf.data(), b"HEADPKTTAIL");
+    }
+}